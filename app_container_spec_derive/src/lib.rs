@@ -0,0 +1,251 @@
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro2::{Ident, TokenStream};
+use syn::{Data, DeriveInput, Fields, GenericArgument, Lit, Meta, NestedMeta, PathArguments, Type};
+
+/*
+ * `#[derive(Parseable)]` generates the boilerplate every hand-written `from_json` in this crate
+ * already repeats: match the JSON object, accumulate an `Errors::Object` of per-field failures,
+ * and only build the struct once every field has parsed cleanly.
+ *
+ * Fields opt into non-default behavior with `#[ac(...)]`:
+ *   - `key = "..."`   the JSON object key to read (defaults to the field name)
+ *   - `required`      a missing key is an error instead of leaving the field `None`
+ *   - `array`         the field is a `Vec<T>`/`Option<Vec<T>>` parsed element-by-element,
+ *                      reporting per-index failures as `Errors::Array`
+ *   - `default = ...` the literal to fall back to when the key is absent (for non-`Option`,
+ *                      non-`required` fields)
+ */
+#[proc_macro_derive(Parseable, attributes(ac))]
+pub fn derive_parseable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+
+    let fields = match ast.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => panic!("#[derive(Parseable)] only supports structs with named fields"),
+        },
+        _ => panic!("#[derive(Parseable)] only supports structs"),
+    };
+
+    let configs: Vec<FieldConfig> = fields.iter().map(FieldConfig::from_field).collect();
+
+    let initializers = configs.iter().map(FieldConfig::initializer);
+    let parsers = configs.iter().map(FieldConfig::parser);
+    let idents = configs.iter().map(|config| config.ident.clone());
+    let finalizers = configs.iter().map(FieldConfig::finalizer);
+
+    let expanded = quote! {
+        impl ::util::Parseable for #name {
+            fn from_json(json: &::serde_json::Value) -> ::util::ParseResult<#name> {
+                match json {
+                    &::serde_json::Value::Object(ref obj) => {
+                        let mut errors = ::std::collections::BTreeMap::new();
+                        #(#initializers)*
+                        #(#parsers)*
+
+                        if errors.is_empty() {
+                            Ok(#name {
+                                #(#idents: #finalizers),*
+                            })
+                        } else {
+                            Err(::util::Errors::Object(errors))
+                        }
+                    },
+                    _ => Err(::util::Errors::Node(vec![String::from("must be an object")])),
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+enum Mode {
+    // Local starts as `None`; a present-but-invalid or missing (when `required`) value records
+    // an error. `required` fields are unwrapped at the end, optional ones stay `Option<T>`.
+    OptionWrapped { required: bool },
+    // Local starts at a literal default; only a present-and-valid value overwrites it.
+    Default(Lit),
+}
+
+struct FieldConfig {
+    ident: Ident,
+    key: String,
+    array: bool,
+    mode: Mode,
+    element_ty: Option<Type>,
+}
+
+impl FieldConfig {
+    fn from_field(field: &syn::Field) -> FieldConfig {
+        let ident = field.ident.clone().expect("#[derive(Parseable)] requires named fields");
+        let mut key = ident.to_string();
+        let mut required = false;
+        let mut array = false;
+        let mut default = None;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("ac")) {
+            let meta = attr.parse_meta().expect("malformed #[ac(...)] attribute");
+
+            let items = match meta {
+                Meta::List(ref list) => &list.nested,
+                _ => panic!("#[ac(...)] must take a list of items"),
+            };
+
+            for item in items.iter() {
+                match item {
+                    NestedMeta::Meta(Meta::Word(ref word)) if word == "required" => { required = true; },
+                    NestedMeta::Meta(Meta::Word(ref word)) if word == "array" => { array = true; },
+                    NestedMeta::Meta(Meta::NameValue(ref kv)) if kv.ident == "key" => {
+                        key = match kv.lit {
+                            Lit::Str(ref s) => s.value(),
+                            _ => panic!("#[ac(key = \"...\")] must be a string"),
+                        };
+                    },
+                    NestedMeta::Meta(Meta::NameValue(ref kv)) if kv.ident == "default" => {
+                        default = Some(kv.lit.clone());
+                    },
+                    _ => panic!("unrecognized #[ac(...)] item"),
+                }
+            }
+        }
+
+        let mode = match default {
+            Some(lit) => Mode::Default(lit),
+            None => Mode::OptionWrapped { required: required },
+        };
+
+        let element_ty = if array {
+            let (_, inner) = unwrap_option(&field.ty);
+            Some(unwrap_vec(inner).clone())
+        } else {
+            None
+        };
+
+        FieldConfig { ident: ident, key: key, array: array, mode: mode, element_ty: element_ty }
+    }
+
+    fn initializer(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        match self.mode {
+            Mode::OptionWrapped { .. } => quote! { let mut #ident = None; },
+            Mode::Default(ref lit) => quote! { let mut #ident = #lit; },
+        }
+    }
+
+    fn parser(&self) -> TokenStream {
+        let ident = &self.ident;
+        let key = &self.key;
+
+        let missing = match self.mode {
+            Mode::OptionWrapped { required: true } => {
+                quote! { errors.insert(String::from(#key), ::util::Errors::Node(vec![String::from("must be defined")])); }
+            },
+            _ => quote! {},
+        };
+
+        if self.array {
+            let element_ty = self.element_ty.as_ref().expect("#[ac(array)] field must be a Vec");
+
+            let assign = match self.mode {
+                Mode::OptionWrapped { .. } => quote! { #ident = Some(result); },
+                Mode::Default(_) => quote! { #ident = result; },
+            };
+
+            quote! {
+                match obj.get(#key) {
+                    Some(&::serde_json::Value::Array(ref arr)) => {
+                        let mut result = vec![];
+                        let mut element_errors = vec![];
+
+                        for item in arr.iter() {
+                            match <#element_ty as ::util::Parseable>::from_json(item) {
+                                Ok(value) => {
+                                    element_errors.push(None);
+                                    result.push(value);
+                                },
+                                Err(err) => { element_errors.push(Some(err)); },
+                            }
+                        }
+
+                        if element_errors.iter().any(|err| err.is_some()) {
+                            errors.insert(String::from(#key), ::util::Errors::Array(element_errors));
+                        } else {
+                            #assign
+                        }
+                    },
+                    Some(_) => {
+                        errors.insert(String::from(#key), ::util::Errors::Node(vec![String::from("must be an array")]));
+                    },
+                    None => { #missing },
+                }
+            }
+        } else {
+            let assign = match self.mode {
+                Mode::OptionWrapped { .. } => quote! { #ident = Some(value); },
+                Mode::Default(_) => quote! { #ident = value; },
+            };
+
+            quote! {
+                match obj.get(#key) {
+                    Some(field_json) => {
+                        match ::util::Parseable::from_json(field_json) {
+                            Ok(value) => { #assign },
+                            Err(err) => { errors.insert(String::from(#key), err); },
+                        }
+                    },
+                    None => { #missing },
+                }
+            }
+        }
+    }
+
+    fn finalizer(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        match self.mode {
+            Mode::OptionWrapped { required: true } => quote! { #ident.unwrap() },
+            Mode::OptionWrapped { required: false } => quote! { #ident },
+            Mode::Default(_) => quote! { #ident },
+        }
+    }
+}
+
+fn unwrap_option(ty: &Type) -> (bool, &Type) {
+    if let Type::Path(ref path) = *ty {
+        let segment = path.path.segments.last().unwrap().into_value();
+
+        if segment.ident == "Option" {
+            if let PathArguments::AngleBracketed(ref args) = segment.arguments {
+                if let Some(&GenericArgument::Type(ref inner)) = args.args.first().map(|pair| pair.into_value()) {
+                    return (true, inner);
+                }
+            }
+        }
+    }
+
+    (false, ty)
+}
+
+fn unwrap_vec(ty: &Type) -> &Type {
+    if let Type::Path(ref path) = *ty {
+        let segment = path.path.segments.last().unwrap().into_value();
+
+        if segment.ident == "Vec" {
+            if let PathArguments::AngleBracketed(ref args) = segment.arguments {
+                if let Some(&GenericArgument::Type(ref inner)) = args.args.first().map(|pair| pair.into_value()) {
+                    return inner;
+                }
+            }
+        }
+    }
+
+    panic!("#[ac(array)] field must be a Vec<T> or Option<Vec<T>>")
+}
@@ -1,12 +1,12 @@
+use app_container_spec_derive::Parseable;
 use image_manifest::app::environment_variable::EnvironmentVariable;
 use image_manifest::app::event_handler::EventHandler;
 use image_manifest::app::mount_point::MountPoint;
 use image_manifest::app::port::Port;
-use rustc_serialize::json::Json;
-use std::collections::BTreeMap;
+use serde_json::{Map, Value};
 use types::{ACIdentifier, ACKind, ACName, ACVersion, ImageID, Isolator, Timestamps};
 use url::Url;
-use util::{Errors, Parseable, ParseResult, StringWrapper};
+use util::Serializable;
 
 pub enum Annotation {
     Authors {
@@ -31,66 +31,104 @@ pub enum Annotation {
     },
 }
 
+impl Serializable for Annotation {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        match *self {
+            Annotation::Authors { ref name, ref value } => {
+                obj.insert(String::from("name"), name.to_json());
+                obj.insert(String::from("value"), value.to_json());
+            },
+            Annotation::Created { ref name, ref value } => {
+                obj.insert(String::from("name"), name.to_json());
+                obj.insert(String::from("value"), value.to_json());
+            },
+            Annotation::Documentation { ref name, ref value } => {
+                obj.insert(String::from("name"), name.to_json());
+                obj.insert(String::from("value"), Value::String(value.to_string()));
+            },
+            Annotation::Homepage { ref name, ref value } => {
+                obj.insert(String::from("name"), name.to_json());
+                obj.insert(String::from("value"), Value::String(value.to_string()));
+            },
+            Annotation::Normal { ref name, ref value } => {
+                obj.insert(String::from("name"), name.to_json());
+                obj.insert(String::from("value"), value.to_json());
+            },
+        }
+
+        Value::Object(obj)
+    }
+}
+
+#[derive(Parseable)]
 pub struct App {
+    #[ac(array)]
     environment: Option<Vec<EnvironmentVariable>>,
+    #[ac(key = "eventHandlers", array)]
     event_handlers: Option<Vec<EventHandler>>,
+    #[ac(array)]
     exec: Option<Vec<String>>,
     group: Option<String>,
+    #[ac(array)]
     isolators: Option<Vec<Isolator>>,
+    #[ac(key = "mountPoints", array)]
     mount_points: Option<Vec<MountPoint>>,
+    #[ac(array)]
     ports: Option<Vec<Port>>,
+    #[ac(key = "supplementaryGIDs", array)]
     supplementary_gids: Option<Vec<u64>>,
     user: Option<String>,
+    #[ac(key = "workingDirectory")]
     working_directory: Option<String>,
 }
 
-impl Parseable for App {
-    fn from_json(json: &Json) -> ParseResult<App> {
-        match json {
-            &Json::Object(ref obj) => {
-                let mut errors = BTreeMap::new();
-                let mut environment = None;
-                let mut event_handlers = None;
-                let mut exec = None;
-                let mut group = None;
-                let mut isolators = None;
-                let mut mount_points = None;
-                let mut ports = None;
-                let mut supplementary_gids = None;
-                let mut user = None;
-                let mut working_directory = None;
-
-                match obj.get("environment") {
-                    Some(&Json::Array(ref arr)) => {
-                        let result = vec![];
-                        let environment_errors = vec![];
-
-                        for i in 0..arr.len() {
-                            let ref var_json = arr[i];
-
-                            match EnvironmentVariable::from_json(var_json) {
-                                Ok(var) => {
-                                    environment_errors.push(None);
-                                    result.push(var);
-                                },
-                                Err(err) => { environment_errors.push(Some(err)); },
-                            }
-                        }
-
-                        if environment_errors.iter().any(|&e| e.is_some()) {
-                            errors.insert(String::from("environment"), Errors::Array(environment_errors));
-                        } else {
-                            environment = Some(result);
-                        }
-                    },
-                    Some(_) => {
-                        errors.insert(String::from("environment"), Errors::Node(vec![String::from("must be an array")]));
-                    },
-                    None => {},
-                }
-            },
-            _ => Err(Errors::Node(vec![String::from("must be an object")])),
+impl Serializable for App {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        if let Some(ref environment) = self.environment {
+            obj.insert(String::from("environment"), Value::Array(environment.iter().map(|e| e.to_json()).collect()));
+        }
+
+        if let Some(ref event_handlers) = self.event_handlers {
+            obj.insert(String::from("eventHandlers"), Value::Array(event_handlers.iter().map(|e| e.to_json()).collect()));
+        }
+
+        if let Some(ref exec) = self.exec {
+            obj.insert(String::from("exec"), Value::Array(exec.iter().map(|e| e.to_json()).collect()));
+        }
+
+        if let Some(ref group) = self.group {
+            obj.insert(String::from("group"), group.to_json());
+        }
+
+        if let Some(ref isolators) = self.isolators {
+            obj.insert(String::from("isolators"), Value::Array(isolators.iter().map(|i| i.to_json()).collect()));
+        }
+
+        if let Some(ref mount_points) = self.mount_points {
+            obj.insert(String::from("mountPoints"), Value::Array(mount_points.iter().map(|m| m.to_json()).collect()));
+        }
+
+        if let Some(ref ports) = self.ports {
+            obj.insert(String::from("ports"), Value::Array(ports.iter().map(|p| p.to_json()).collect()));
+        }
+
+        if let Some(ref supplementary_gids) = self.supplementary_gids {
+            obj.insert(String::from("supplementaryGIDs"), Value::Array(supplementary_gids.iter().map(|g| Value::from(*g)).collect()));
+        }
+
+        if let Some(ref user) = self.user {
+            obj.insert(String::from("user"), user.to_json());
+        }
+
+        if let Some(ref working_directory) = self.working_directory {
+            obj.insert(String::from("workingDirectory"), working_directory.to_json());
         }
+
+        Value::Object(obj)
     }
 }
 
@@ -99,6 +137,15 @@ pub struct Label {
     value: String,
 }
 
+impl Serializable for Label {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("value"), self.value.to_json());
+        Value::Object(obj)
+    }
+}
+
 pub struct Dependency {
     image_id: Option<ImageID>,
     image_name: ACIdentifier,
@@ -106,6 +153,28 @@ pub struct Dependency {
     size: Option<u64>,
 }
 
+impl Serializable for Dependency {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        if let Some(ref image_id) = self.image_id {
+            obj.insert(String::from("imageID"), image_id.to_json());
+        }
+
+        obj.insert(String::from("imageName"), self.image_name.to_json());
+
+        if let Some(ref labels) = self.labels {
+            obj.insert(String::from("labels"), Value::Array(labels.iter().map(|l| l.to_json()).collect()));
+        }
+
+        if let Some(size) = self.size {
+            obj.insert(String::from("size"), Value::from(size));
+        }
+
+        Value::Object(obj)
+    }
+}
+
 pub struct ImageManifest {
     ac_kind: ACKind,
     ac_version: ACVersion,
@@ -116,3 +185,33 @@ pub struct ImageManifest {
     name: ACIdentifier,
     path_whitelist: Vec<String>,
 }
+
+impl Serializable for ImageManifest {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        obj.insert(String::from("acKind"), self.ac_kind.to_json());
+        obj.insert(String::from("acVersion"), self.ac_version.to_json());
+
+        if let Some(ref annotations) = self.annotations {
+            obj.insert(String::from("annotations"), annotations.to_json());
+        }
+
+        if let Some(ref app) = self.app {
+            obj.insert(String::from("app"), app.to_json());
+        }
+
+        if let Some(ref dependencies) = self.dependencies {
+            obj.insert(String::from("dependencies"), Value::Array(dependencies.iter().map(|d| d.to_json()).collect()));
+        }
+
+        if let Some(ref labels) = self.labels {
+            obj.insert(String::from("labels"), Value::Array(labels.iter().map(|l| l.to_json()).collect()));
+        }
+
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("pathWhitelist"), Value::Array(self.path_whitelist.iter().map(|p| p.to_json()).collect()));
+
+        Value::Object(obj)
+    }
+}
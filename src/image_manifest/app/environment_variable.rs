@@ -1,7 +1,7 @@
+use app_container_spec_derive::Parseable;
 use regex::Regex;
-use rustc_serialize::json::Json;
-use std::collections::BTreeMap;
-use util::{Errors, Parseable, ParseResult, StringWrapper};
+use serde_json::{Map, Value};
+use util::{Errors, ParseResult, Renderable, Serializable, StringWrapper};
 
 lazy_static! {
     static ref ENVIRONMENT_VARIABLE_NAME_REGEX: Regex = Regex::new("^[a-zA-Z][a-zA-Z0-9_]*$").unwrap();
@@ -19,50 +19,25 @@ impl StringWrapper for EnvironmentVariableName {
     }
 }
 
+impl Renderable for EnvironmentVariableName {
+    fn to_string_repr(&self) -> String {
+        self.0.clone()
+    }
+}
+
+#[derive(Parseable)]
 pub struct EnvironmentVariable {
+    #[ac(required)]
     name: EnvironmentVariableName,
+    #[ac(required)]
     value: String,
 }
 
-impl Parseable for EnvironmentVariable {
-    fn from_json(json: &Json) -> ParseResult<EnvironmentVariable> {
-        match json {
-            &Json::Object(ref obj) => {
-                let mut errors = BTreeMap::new();
-                let mut name = None;
-                let mut value = None;
-
-                match obj.get("name") {
-                    Some(name_json) => {
-                        match EnvironmentVariableName::from_json(name_json) {
-                            Ok(n) => { name = Some(n); },
-                            Err(err) => { errors.insert(String::from("name"), err); },
-                        };
-                    },
-                    None => {
-                        errors.insert(String::from("name"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                };
-
-                match obj.get("value") {
-                    Some(value_json) => {
-                        match String::from_json(value_json) {
-                            Ok(v) => { value = Some(v); },
-                            Err(err) => { errors.insert(String::from("value"), err); },
-                        };
-                    },
-                    None => {
-                        errors.insert(String::from("value"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                }
-
-                if errors.is_empty() {
-                    Ok(EnvironmentVariable { name: name.unwrap(), value: value.unwrap() })
-                } else {
-                    Err(Errors::Object(errors))
-                }
-            },
-            _ => Err(Errors::Node(vec![String::from("must be an object")])),
-        }
+impl Serializable for EnvironmentVariable {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("value"), self.value.to_json());
+        Value::Object(obj)
     }
 }
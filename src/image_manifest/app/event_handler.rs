@@ -1,5 +1,6 @@
-use rustc_serialize::json::Json;
-use util::{Errors, Parseable, ParseResult, StringWrapper};
+use app_container_spec_derive::Parseable;
+use serde_json::{Map, Value};
+use util::{Errors, ParseResult, Renderable, Serializable, StringWrapper};
 
 pub enum EventHandlerName {
     PreStart,
@@ -18,78 +19,28 @@ impl StringWrapper for EventHandlerName {
     }
 }
 
+impl Renderable for EventHandlerName {
+    fn to_string_repr(&self) -> String {
+        match *self {
+            EventHandlerName::PreStart => String::from("pre-start"),
+            EventHandlerName::PostStop => String::from("post-stop"),
+        }
+    }
+}
+
+#[derive(Parseable)]
 pub struct EventHandler {
+    #[ac(required, array)]
     exec: Vec<String>,
+    #[ac(required)]
     name: EventHandlerName,
 }
 
-fn exec_from_json(json: &Json) -> ParseResult<Vec<String>> {
-    match json {
-        &Json::Array(ref arr) => {
-            let mut result = vec![];
-            let mut errors = vec![];
-
-            for i in 0..arr.len() {
-                let ref cmd_json = arr[i];
-
-                match String::from_json(cmd_json) {
-                    Ok(cmd) => {
-                        errors.push(None);
-                        result.push(cmd);
-                    },
-                    Err(err) => { errors.push(Some(err)); },
-                }
-            }
-
-            if errors.iter().any(|&e| e.is_some()) {
-                Err(Errors::Array(errors))
-            } else {
-                Ok(result)
-            }
-        },
-        _ => Err(Errors::Node(vec![String::from("must be an array")])),
-    }
-}
-
-impl Parseable for EventHandler {
-    fn from_json(json: &Json) -> ParseResult<EventHandler> {
-        match json {
-            &Json::Object(ref obj) => {
-                let mut errors = BTreeMap::new();
-                let mut name = None;
-                let mut exec = None;
-
-                match obj.get("name") {
-                    Some(name_json) => {
-                        match EventHandlerName::from_json(name_json) {
-                            Ok(n) => { name = Some(n); },
-                            Err(err) => { errors.insert(String::from("name"), err); },
-                        };
-                    },
-                    None => {
-                        errors.insert(String::from("name"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                };
-
-                match obj.get("exec") {
-                    Some(exec_json) => {
-                        match exec_from_json(exec_json) {
-                            Ok(e) => { exec = Some(e); },
-                            Err(err) => { errors.insert(String::from("exec"), err); },
-                        }
-                    },
-                    None => {
-                        errors.insert(String::from("exec"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                };
-
-                if errors.is_empty() {
-                    Ok(EventHandler { name: name.unwrap(), exec: exec.unwrap() })
-                } else {
-                    Err(Errors::Object(errors))
-                }
-            },
-            _ => Err(Errors::Node(vec![String::from("must be an object")])),
-        }
+impl Serializable for EventHandler {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("exec"), Value::Array(self.exec.iter().map(|e| e.to_json()).collect()));
+        Value::Object(obj)
     }
 }
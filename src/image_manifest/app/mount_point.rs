@@ -1,64 +1,24 @@
-use rustc_serialize::json::Json;
-use std::collections::BTreeMap;
+use app_container_spec_derive::Parseable;
+use serde_json::{Map, Value};
 use types::ACName;
-use util::{Errors, Parseable, ParseResult};
+use util::Serializable;
 
+#[derive(Parseable)]
 pub struct MountPoint {
+    #[ac(required)]
     name: ACName,
+    #[ac(required)]
     path: String,
+    #[ac(key = "readOnly", default = false)]
     read_only: bool,
 }
 
-impl Parseable for MountPoint {
-    fn from_json(json: &Json) -> ParseResult<MountPoint> {
-        match json {
-            &Json::Object(ref obj) => {
-                let mut errors = BTreeMap::new();
-                let mut name = None;
-                let mut path = None;
-                let mut read_only = false;
-
-                match obj.get("name") {
-                    Some(name_json) => {
-                        match ACName::from_json(name_json) {
-                            Ok(n) => { name = Some(n); },
-                            Err(err) => { errors.insert(String::from("name"), err); },
-                        }
-                    },
-                    None => {
-                        errors.insert(String::from("name"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                };
-
-                match obj.get("path") {
-                    Some(path_json) => {
-                        match String::from_json(path_json) {
-                            Ok(p) => { path = Some(p); },
-                            Err(err) => { errors.insert(String::from("path"), err); },
-                        }
-                    },
-                    None => {
-                        errors.insert(String::from("path"), Errors::Node(vec![String::from("must be defined")]));
-                    },
-                };
-
-                match obj.get("readOnly") {
-                    Some(&Json::Boolean(ref ro)) => { read_only = *ro; },
-                    Some(_) => { errors.insert(String::from("readOnly"), Errors::Node(vec![String::from("must be a boolean")])); },
-                    None => {},
-                };
-
-                if errors.is_empty() {
-                    Ok(MountPoint {
-                        name: name.unwrap(),
-                        path: path.unwrap(),
-                        read_only: read_only
-                    })
-                } else {
-                    Err(Errors::Object(errors))
-                }
-            },
-            _ => Err(Errors::Node(vec![String::from("must be an object")])),
-        }
+impl Serializable for MountPoint {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("path"), self.path.to_json());
+        obj.insert(String::from("readOnly"), Value::Bool(self.read_only));
+        Value::Object(obj)
     }
 }
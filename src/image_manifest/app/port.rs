@@ -1,7 +1,7 @@
-use rustc_serialize::json::Json;
+use serde_json::{Map, Value};
 use std::collections::BTreeMap;
 use types::ACName;
-use util::{Errors, Parseable, ParseResult};
+use util::{Errors, Parseable, ParseResult, Serializable};
 
 pub struct Port {
     count: u64,
@@ -12,9 +12,9 @@ pub struct Port {
 }
 
 impl Parseable for Port {
-    fn from_json(json: &Json) -> ParseResult<Port> {
+    fn from_json(json: &Value) -> ParseResult<Port> {
         match json {
-            &Json::Object(ref obj) => {
+            &Value::Object(ref obj) => {
                 let mut errors = BTreeMap::new();
                 let mut count = 1;
                 let mut name = None;
@@ -23,11 +23,18 @@ impl Parseable for Port {
                 let mut socket_activated = false;
 
                 match obj.get("count") {
-                    Some(&Json::U64(ref c)) => {
-                        if (*c) < 1 {
-                            errors.insert(String::from("count"), Errors::Node(vec![String::from("must be >= 1")]));
-                        } else {
-                            count = *c;
+                    Some(&Value::Number(ref n)) => {
+                        match n.as_u64() {
+                            Some(c) => {
+                                if c < 1 {
+                                    errors.insert(String::from("count"), Errors::Node(vec![String::from("must be >= 1")]));
+                                } else {
+                                    count = c;
+                                }
+                            },
+                            None => {
+                                errors.insert(String::from("count"), Errors::Node(vec![String::from("must be a positive integer")]));
+                            },
                         }
                     },
                     Some(_) => {
@@ -49,11 +56,18 @@ impl Parseable for Port {
                 };
 
                 match obj.get("port") {
-                    Some(&Json::U64(ref p)) => {
-                        if (*p) < 1 || (*p) > 65535 {
-                            errors.insert(String::from("port"), Errors::Node(vec![String::from("must be >= 1 and <= 65535")]));
-                        } else {
-                            port = Some(*p as u16);
+                    Some(&Value::Number(ref n)) => {
+                        match n.as_u64() {
+                            Some(p) => {
+                                if p < 1 || p > 65535 {
+                                    errors.insert(String::from("port"), Errors::Node(vec![String::from("must be >= 1 and <= 65535")]));
+                                } else {
+                                    port = Some(p as u16);
+                                }
+                            },
+                            None => {
+                                errors.insert(String::from("port"), Errors::Node(vec![String::from("must be a positive integer")]));
+                            },
                         }
                     },
                     Some(_) => {
@@ -77,7 +91,7 @@ impl Parseable for Port {
                 };
 
                 match obj.get("socketActivated") {
-                    Some(&Json::Boolean(ref sa)) => { socket_activated = *sa; },
+                    Some(&Value::Bool(ref sa)) => { socket_activated = *sa; },
                     Some(_) => {
                         errors.insert(String::from("socketActivated"), Errors::Node(vec![String::from("must be a boolean")]));
                     },
@@ -100,3 +114,15 @@ impl Parseable for Port {
         }
     }
 }
+
+impl Serializable for Port {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("count"), Value::from(self.count));
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("port"), Value::from(self.port));
+        obj.insert(String::from("protocol"), self.protocol.to_json());
+        obj.insert(String::from("socketActivated"), Value::Bool(self.socket_activated));
+        Value::Object(obj)
+    }
+}
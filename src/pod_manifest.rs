@@ -1,23 +1,60 @@
 use image_manifest;
-use rustc_serialize::json::Json;
+use serde_json::{Map, Value};
 use types::{ACIdentifier, ACKind, ACName, ACVersion, ImageID, Isolator};
+use util::{Renderable, Serializable};
 
 pub struct Annotation {
     name: ACName,
     value: String,
 }
 
+impl Serializable for Annotation {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("value"), self.value.to_json());
+        Value::Object(obj)
+    }
+}
+
 pub struct Image {
     id: ImageID,
-    labels: Option<Vec<Json>>,
+    labels: Option<Vec<Value>>,
     name: Option<ACIdentifier>,
 }
 
+impl Serializable for Image {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        obj.insert(String::from("id"), self.id.to_json());
+
+        if let Some(ref labels) = self.labels {
+            obj.insert(String::from("labels"), Value::Array(labels.clone()));
+        }
+
+        if let Some(ref name) = self.name {
+            obj.insert(String::from("name"), name.to_json());
+        }
+
+        Value::Object(obj)
+    }
+}
+
 pub struct Mount {
     path: String,
     volume: ACName,
 }
 
+impl Serializable for Mount {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("path"), self.path.to_json());
+        obj.insert(String::from("volume"), self.volume.to_json());
+        Value::Object(obj)
+    }
+}
+
 pub struct App {
     annotations: Option<Vec<Annotation>>,
     app: Option<image_manifest::App>,
@@ -26,16 +63,58 @@ pub struct App {
     name: ACName,
 }
 
+impl Serializable for App {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        if let Some(ref annotations) = self.annotations {
+            obj.insert(String::from("annotations"), Value::Array(annotations.iter().map(|a| a.to_json()).collect()));
+        }
+
+        if let Some(ref app) = self.app {
+            obj.insert(String::from("app"), app.to_json());
+        }
+
+        obj.insert(String::from("image"), self.image.to_json());
+
+        if let Some(ref mounts) = self.mounts {
+            obj.insert(String::from("mounts"), Value::Array(mounts.iter().map(|m| m.to_json()).collect()));
+        }
+
+        obj.insert(String::from("name"), self.name.to_json());
+
+        Value::Object(obj)
+    }
+}
+
 pub struct Port {
     name: ACName,
     host_port: u64,
 }
 
+impl Serializable for Port {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("hostPort"), Value::from(self.host_port));
+        Value::Object(obj)
+    }
+}
+
 pub enum Kind {
     Empty,
     Host,
 }
 
+impl Renderable for Kind {
+    fn to_string_repr(&self) -> String {
+        match *self {
+            Kind::Empty => String::from("empty"),
+            Kind::Host => String::from("host"),
+        }
+    }
+}
+
 pub struct Volume {
     gid: u64,
     kind: Kind,
@@ -46,6 +125,26 @@ pub struct Volume {
     uid: u64,
 }
 
+impl Serializable for Volume {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        obj.insert(String::from("gid"), Value::from(self.gid));
+        obj.insert(String::from("kind"), self.kind.to_json());
+        obj.insert(String::from("mode"), self.mode.to_json());
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("readOnly"), Value::Bool(self.read_only));
+
+        if let Some(ref source) = self.source {
+            obj.insert(String::from("source"), source.to_json());
+        }
+
+        obj.insert(String::from("uid"), Value::from(self.uid));
+
+        Value::Object(obj)
+    }
+}
+
 pub struct PodManifest {
     ac_kind: ACKind,
     ac_version: ACVersion,
@@ -55,3 +154,32 @@ pub struct PodManifest {
     ports: Option<Vec<Port>>,
     volumes: Option<Vec<Volume>>,
 }
+
+impl Serializable for PodManifest {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        obj.insert(String::from("acKind"), self.ac_kind.to_json());
+        obj.insert(String::from("acVersion"), self.ac_version.to_json());
+
+        if let Some(ref annotations) = self.annotations {
+            obj.insert(String::from("annotations"), Value::Array(annotations.iter().map(|a| a.to_json()).collect()));
+        }
+
+        obj.insert(String::from("apps"), Value::Array(self.apps.iter().map(|a| a.to_json()).collect()));
+
+        if let Some(ref isolators) = self.isolators {
+            obj.insert(String::from("isolators"), Value::Array(isolators.iter().map(|i| i.to_json()).collect()));
+        }
+
+        if let Some(ref ports) = self.ports {
+            obj.insert(String::from("ports"), Value::Array(ports.iter().map(|p| p.to_json()).collect()));
+        }
+
+        if let Some(ref volumes) = self.volumes {
+            obj.insert(String::from("volumes"), Value::Array(volumes.iter().map(|v| v.to_json()).collect()));
+        }
+
+        Value::Object(obj)
+    }
+}
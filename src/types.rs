@@ -1,8 +1,11 @@
+use app_container_spec_derive::Parseable;
 use chrono::{DateTime, FixedOffset};
 use regex::Regex;
-use rustc_serialize::json::Json;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use std::collections::BTreeMap;
-use util::{Errors, Parseable, ParseResult, StringWrapper};
+use std::io::{self, Read};
+use util::{Errors, Parseable, ParseResult, Renderable, Serializable, StringWrapper};
 
 lazy_static! {
     // Regex taken from https://github.com/appc/spec/blob/master/spec/types.md#ac-identifier-type
@@ -11,6 +14,9 @@ lazy_static! {
     static ref AC_NAME_REGEX: Regex = Regex::new("^[a-z0-9]+([-][a-z0-9]+)*$").unwrap();
     static ref SEMVER_REGEX: Regex = Regex::new("^(?P<major>\\d|([1-9]\\d*))\\.(?P<minor>\\d|([1-9]\\d*))\\.(?P<patch>\\d|([1-9]\\d*))$").unwrap();
     static ref IMAGE_ID_REGEX: Regex = Regex::new("^(?P<hash>[^-]+)-(?P<value>[0-9A-Fa-f]+)$").unwrap();
+    // A decimal quantity with an optional binary-SI (Ki/Mi/Gi/Ti), decimal-SI (k/M/G/T), or
+    // milli (m) suffix, as used by the `resource/*` isolators.
+    static ref RESOURCE_QUANTITY_REGEX: Regex = Regex::new("^(?P<value>\\d+(\\.\\d+)?)(?P<suffix>Ki|Mi|Gi|Ti|k|M|G|T|m)?$").unwrap();
 }
 
 pub struct ACIdentifier(String);
@@ -25,6 +31,12 @@ impl StringWrapper for ACIdentifier {
     }
 }
 
+impl Renderable for ACIdentifier {
+    fn to_string_repr(&self) -> String {
+        self.0.clone()
+    }
+}
+
 pub enum ACKind {
     ImageManifest,
     PodManifest
@@ -42,6 +54,15 @@ impl StringWrapper for ACKind {
     }
 }
 
+impl Renderable for ACKind {
+    fn to_string_repr(&self) -> String {
+        match *self {
+            ACKind::ImageManifest => String::from("ImageManifest"),
+            ACKind::PodManifest => String::from("PodManifest"),
+        }
+    }
+}
+
 pub struct ACName(String);
 
 impl StringWrapper for ACName {
@@ -54,6 +75,12 @@ impl StringWrapper for ACName {
     }
 }
 
+impl Renderable for ACName {
+    fn to_string_repr(&self) -> String {
+        self.0.clone()
+    }
+}
+
 pub struct ACVersion {
     major: u64,
     minor: u64,
@@ -77,10 +104,29 @@ impl StringWrapper for ACVersion {
     }
 }
 
+impl Renderable for ACVersion {
+    fn to_string_repr(&self) -> String {
+        format!("{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 pub enum HashAlgorithm {
+    SHA256,
+    SHA384,
     SHA512,
 }
 
+impl HashAlgorithm {
+    // The length of the hex-encoded digest this algorithm produces.
+    fn hex_len(&self) -> usize {
+        match *self {
+            HashAlgorithm::SHA256 => 64,
+            HashAlgorithm::SHA384 => 96,
+            HashAlgorithm::SHA512 => 128,
+        }
+    }
+}
+
 pub struct ImageID {
     hash: HashAlgorithm,
     value: String
@@ -90,13 +136,24 @@ impl StringWrapper for ImageID {
     fn from_string(s: &String) -> ParseResult<ImageID> {
         match IMAGE_ID_REGEX.captures(s) {
             Some(captures) => {
-                if captures.name("hash").unwrap() == "sha512" {
-                    Ok(ImageID {
-                        hash: HashAlgorithm::SHA512,
-                        value: String::from(captures.name("value").unwrap())
-                    })
-                } else {
-                    Err(Errors::Node(vec![String::from("must be a valid Image ID (invalid hash algorithm)")]))
+                let hash = match captures.name("hash").unwrap() {
+                    "sha256" => Some(HashAlgorithm::SHA256),
+                    "sha384" => Some(HashAlgorithm::SHA384),
+                    "sha512" => Some(HashAlgorithm::SHA512),
+                    _ => None,
+                };
+
+                match hash {
+                    Some(hash) => {
+                        let value = captures.name("value").unwrap().to_lowercase();
+
+                        if value.len() == hash.hex_len() {
+                            Ok(ImageID { hash: hash, value: value })
+                        } else {
+                            Err(Errors::Node(vec![String::from("must be a valid Image ID (wrong length for hash algorithm)")]))
+                        }
+                    },
+                    None => Err(Errors::Node(vec![String::from("must be a valid Image ID (invalid hash algorithm)")])),
                 }
             },
             None => Err(Errors::Node(vec![String::from("must be a valid Image ID")])),
@@ -104,35 +161,347 @@ impl StringWrapper for ImageID {
     }
 }
 
+impl Renderable for ImageID {
+    fn to_string_repr(&self) -> String {
+        match self.hash {
+            HashAlgorithm::SHA256 => format!("sha256-{}", self.value),
+            HashAlgorithm::SHA384 => format!("sha384-{}", self.value),
+            HashAlgorithm::SHA512 => format!("sha512-{}", self.value),
+        }
+    }
+}
+
+impl ImageID {
+    // Streams `reader` through the digest named by `self.hash` and compares the result against
+    // `self.value` in constant time, so this can be used to verify an actual image tarball
+    // against the `ImageID` it claims to match.
+    pub fn verify(&self, mut reader: impl Read) -> ParseResult<()> {
+        let digest = match self.hash {
+            HashAlgorithm::SHA256 => {
+                let mut hasher = Sha256::new();
+                io::copy(&mut reader, &mut hasher).map_err(|_| Errors::Node(vec![String::from("could not be read")]))?;
+                to_hex(hasher.result().as_slice())
+            },
+            HashAlgorithm::SHA384 => {
+                let mut hasher = Sha384::new();
+                io::copy(&mut reader, &mut hasher).map_err(|_| Errors::Node(vec![String::from("could not be read")]))?;
+                to_hex(hasher.result().as_slice())
+            },
+            HashAlgorithm::SHA512 => {
+                let mut hasher = Sha512::new();
+                io::copy(&mut reader, &mut hasher).map_err(|_| Errors::Node(vec![String::from("could not be read")]))?;
+                to_hex(hasher.result().as_slice())
+            },
+        };
+
+        if constant_time_eq(digest.as_bytes(), self.value.as_bytes()) {
+            Ok(())
+        } else {
+            Err(Errors::Node(vec![String::from("does not match the computed digest of its content")]))
+        }
+    }
+}
+
+// `GenericArray<u8, N>` (the digest type `sha2`'s hashers return) doesn't implement
+// `fmt::LowerHex`, so we lowercase-hex-encode it by hand.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// Stores a resource quantity in "millis" (thousandths of a base unit), the same trick
+// Kubernetes' `resource.Quantity` uses to represent fractional amounts (e.g. the `m` suffix)
+// without resorting to floating point math once parsed.
+pub struct ResourceQuantity(u64);
+
+impl StringWrapper for ResourceQuantity {
+    fn from_string(s: &String) -> ParseResult<ResourceQuantity> {
+        match RESOURCE_QUANTITY_REGEX.captures(s) {
+            Some(captures) => {
+                let value = captures.name("value").unwrap().parse::<f64>().unwrap();
+
+                let millis = match captures.name("suffix") {
+                    Some("Ki") => value * (1u64 << 10) as f64 * 1000.0,
+                    Some("Mi") => value * (1u64 << 20) as f64 * 1000.0,
+                    Some("Gi") => value * (1u64 << 30) as f64 * 1000.0,
+                    Some("Ti") => value * (1u64 << 40) as f64 * 1000.0,
+                    Some("k") => value * 1_000.0 * 1000.0,
+                    Some("M") => value * 1_000_000.0 * 1000.0,
+                    Some("G") => value * 1_000_000_000.0 * 1000.0,
+                    Some("T") => value * 1_000_000_000_000.0 * 1000.0,
+                    Some("m") => value,
+                    Some(_) | None => value * 1000.0,
+                };
+
+                Ok(ResourceQuantity(millis.round() as u64))
+            },
+            None => Err(Errors::Node(vec![String::from("must be a valid resource quantity")])),
+        }
+    }
+}
+
+impl Renderable for ResourceQuantity {
+    fn to_string_repr(&self) -> String {
+        if self.0 % 1000 == 0 {
+            format!("{}", self.0 / 1000)
+        } else {
+            format!("{}m", self.0)
+        }
+    }
+}
+
+#[derive(Parseable)]
+pub struct ResourceIsolatorValue {
+    request: Option<ResourceQuantity>,
+    limit: Option<ResourceQuantity>,
+}
+
+impl Serializable for ResourceIsolatorValue {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+
+        if let Some(ref request) = self.request {
+            obj.insert(String::from("request"), request.to_json());
+        }
+
+        if let Some(ref limit) = self.limit {
+            obj.insert(String::from("limit"), limit.to_json());
+        }
+
+        Value::Object(obj)
+    }
+}
+
+// The standard Linux capabilities, as listed at https://github.com/appc/spec/blob/master/spec/ace.md#linux-isolators
+pub enum LinuxCapability {
+    Chown,
+    DacOverride,
+    DacReadSearch,
+    Fowner,
+    Fsetid,
+    Kill,
+    Setgid,
+    Setuid,
+    Setpcap,
+    LinuxImmutable,
+    NetBindService,
+    NetBroadcast,
+    NetAdmin,
+    NetRaw,
+    IpcLock,
+    IpcOwner,
+    SysModule,
+    SysRawio,
+    SysChroot,
+    SysPtrace,
+    SysPacct,
+    SysAdmin,
+    SysBoot,
+    SysNice,
+    SysResource,
+    SysTime,
+    SysTtyConfig,
+    Mknod,
+    Lease,
+    AuditWrite,
+    AuditControl,
+    Setfcap,
+    MacOverride,
+    MacAdmin,
+    Syslog,
+    WakeAlarm,
+    BlockSuspend,
+    AuditRead,
+}
+
+impl StringWrapper for LinuxCapability {
+    fn from_string(s: &String) -> ParseResult<LinuxCapability> {
+        match s.as_ref() {
+            "CAP_CHOWN" => Ok(LinuxCapability::Chown),
+            "CAP_DAC_OVERRIDE" => Ok(LinuxCapability::DacOverride),
+            "CAP_DAC_READ_SEARCH" => Ok(LinuxCapability::DacReadSearch),
+            "CAP_FOWNER" => Ok(LinuxCapability::Fowner),
+            "CAP_FSETID" => Ok(LinuxCapability::Fsetid),
+            "CAP_KILL" => Ok(LinuxCapability::Kill),
+            "CAP_SETGID" => Ok(LinuxCapability::Setgid),
+            "CAP_SETUID" => Ok(LinuxCapability::Setuid),
+            "CAP_SETPCAP" => Ok(LinuxCapability::Setpcap),
+            "CAP_LINUX_IMMUTABLE" => Ok(LinuxCapability::LinuxImmutable),
+            "CAP_NET_BIND_SERVICE" => Ok(LinuxCapability::NetBindService),
+            "CAP_NET_BROADCAST" => Ok(LinuxCapability::NetBroadcast),
+            "CAP_NET_ADMIN" => Ok(LinuxCapability::NetAdmin),
+            "CAP_NET_RAW" => Ok(LinuxCapability::NetRaw),
+            "CAP_IPC_LOCK" => Ok(LinuxCapability::IpcLock),
+            "CAP_IPC_OWNER" => Ok(LinuxCapability::IpcOwner),
+            "CAP_SYS_MODULE" => Ok(LinuxCapability::SysModule),
+            "CAP_SYS_RAWIO" => Ok(LinuxCapability::SysRawio),
+            "CAP_SYS_CHROOT" => Ok(LinuxCapability::SysChroot),
+            "CAP_SYS_PTRACE" => Ok(LinuxCapability::SysPtrace),
+            "CAP_SYS_PACCT" => Ok(LinuxCapability::SysPacct),
+            "CAP_SYS_ADMIN" => Ok(LinuxCapability::SysAdmin),
+            "CAP_SYS_BOOT" => Ok(LinuxCapability::SysBoot),
+            "CAP_SYS_NICE" => Ok(LinuxCapability::SysNice),
+            "CAP_SYS_RESOURCE" => Ok(LinuxCapability::SysResource),
+            "CAP_SYS_TIME" => Ok(LinuxCapability::SysTime),
+            "CAP_SYS_TTY_CONFIG" => Ok(LinuxCapability::SysTtyConfig),
+            "CAP_MKNOD" => Ok(LinuxCapability::Mknod),
+            "CAP_LEASE" => Ok(LinuxCapability::Lease),
+            "CAP_AUDIT_WRITE" => Ok(LinuxCapability::AuditWrite),
+            "CAP_AUDIT_CONTROL" => Ok(LinuxCapability::AuditControl),
+            "CAP_SETFCAP" => Ok(LinuxCapability::Setfcap),
+            "CAP_MAC_OVERRIDE" => Ok(LinuxCapability::MacOverride),
+            "CAP_MAC_ADMIN" => Ok(LinuxCapability::MacAdmin),
+            "CAP_SYSLOG" => Ok(LinuxCapability::Syslog),
+            "CAP_WAKE_ALARM" => Ok(LinuxCapability::WakeAlarm),
+            "CAP_BLOCK_SUSPEND" => Ok(LinuxCapability::BlockSuspend),
+            "CAP_AUDIT_READ" => Ok(LinuxCapability::AuditRead),
+            _ => Err(Errors::Node(vec![String::from("must be a valid Linux capability")])),
+        }
+    }
+}
+
+impl Renderable for LinuxCapability {
+    fn to_string_repr(&self) -> String {
+        String::from(match *self {
+            LinuxCapability::Chown => "CAP_CHOWN",
+            LinuxCapability::DacOverride => "CAP_DAC_OVERRIDE",
+            LinuxCapability::DacReadSearch => "CAP_DAC_READ_SEARCH",
+            LinuxCapability::Fowner => "CAP_FOWNER",
+            LinuxCapability::Fsetid => "CAP_FSETID",
+            LinuxCapability::Kill => "CAP_KILL",
+            LinuxCapability::Setgid => "CAP_SETGID",
+            LinuxCapability::Setuid => "CAP_SETUID",
+            LinuxCapability::Setpcap => "CAP_SETPCAP",
+            LinuxCapability::LinuxImmutable => "CAP_LINUX_IMMUTABLE",
+            LinuxCapability::NetBindService => "CAP_NET_BIND_SERVICE",
+            LinuxCapability::NetBroadcast => "CAP_NET_BROADCAST",
+            LinuxCapability::NetAdmin => "CAP_NET_ADMIN",
+            LinuxCapability::NetRaw => "CAP_NET_RAW",
+            LinuxCapability::IpcLock => "CAP_IPC_LOCK",
+            LinuxCapability::IpcOwner => "CAP_IPC_OWNER",
+            LinuxCapability::SysModule => "CAP_SYS_MODULE",
+            LinuxCapability::SysRawio => "CAP_SYS_RAWIO",
+            LinuxCapability::SysChroot => "CAP_SYS_CHROOT",
+            LinuxCapability::SysPtrace => "CAP_SYS_PTRACE",
+            LinuxCapability::SysPacct => "CAP_SYS_PACCT",
+            LinuxCapability::SysAdmin => "CAP_SYS_ADMIN",
+            LinuxCapability::SysBoot => "CAP_SYS_BOOT",
+            LinuxCapability::SysNice => "CAP_SYS_NICE",
+            LinuxCapability::SysResource => "CAP_SYS_RESOURCE",
+            LinuxCapability::SysTime => "CAP_SYS_TIME",
+            LinuxCapability::SysTtyConfig => "CAP_SYS_TTY_CONFIG",
+            LinuxCapability::Mknod => "CAP_MKNOD",
+            LinuxCapability::Lease => "CAP_LEASE",
+            LinuxCapability::AuditWrite => "CAP_AUDIT_WRITE",
+            LinuxCapability::AuditControl => "CAP_AUDIT_CONTROL",
+            LinuxCapability::Setfcap => "CAP_SETFCAP",
+            LinuxCapability::MacOverride => "CAP_MAC_OVERRIDE",
+            LinuxCapability::MacAdmin => "CAP_MAC_ADMIN",
+            LinuxCapability::Syslog => "CAP_SYSLOG",
+            LinuxCapability::WakeAlarm => "CAP_WAKE_ALARM",
+            LinuxCapability::BlockSuspend => "CAP_BLOCK_SUSPEND",
+            LinuxCapability::AuditRead => "CAP_AUDIT_READ",
+        })
+    }
+}
+
+#[derive(Parseable)]
+pub struct CapabilitySetIsolatorValue {
+    #[ac(required, array)]
+    set: Vec<LinuxCapability>,
+}
+
+impl Serializable for CapabilitySetIsolatorValue {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("set"), Value::Array(self.set.iter().map(|c| c.to_json()).collect()));
+        Value::Object(obj)
+    }
+}
+
+// Well-known isolator values get parsed into a typed variant; anything else stays opaque so the
+// parser keeps working against isolator names it doesn't know about yet.
+pub enum IsolatorValue {
+    ResourceMemory(ResourceIsolatorValue),
+    ResourceCPU(ResourceIsolatorValue),
+    ResourceBlockBandwidth(ResourceIsolatorValue),
+    LinuxCapabilitiesRetainSet(CapabilitySetIsolatorValue),
+    LinuxCapabilitiesRemoveSet(CapabilitySetIsolatorValue),
+    Opaque(Value),
+}
+
+impl Serializable for IsolatorValue {
+    fn to_json(&self) -> Value {
+        match *self {
+            IsolatorValue::ResourceMemory(ref v) => v.to_json(),
+            IsolatorValue::ResourceCPU(ref v) => v.to_json(),
+            IsolatorValue::ResourceBlockBandwidth(ref v) => v.to_json(),
+            IsolatorValue::LinuxCapabilitiesRetainSet(ref v) => v.to_json(),
+            IsolatorValue::LinuxCapabilitiesRemoveSet(ref v) => v.to_json(),
+            IsolatorValue::Opaque(ref v) => v.clone(),
+        }
+    }
+}
+
 pub struct Isolator {
     name: ACIdentifier,
-    value: Json,
+    value: IsolatorValue,
+}
+
+impl Isolator {
+    fn parse_value(name: &ACIdentifier, json: &Value) -> ParseResult<IsolatorValue> {
+        match name.to_string_repr().as_ref() {
+            "resource/memory" => ResourceIsolatorValue::from_json(json).map(IsolatorValue::ResourceMemory),
+            "resource/cpu" => ResourceIsolatorValue::from_json(json).map(IsolatorValue::ResourceCPU),
+            "resource/block-bandwidth" => ResourceIsolatorValue::from_json(json).map(IsolatorValue::ResourceBlockBandwidth),
+            "os/linux/capabilities-retain-set" => CapabilitySetIsolatorValue::from_json(json).map(IsolatorValue::LinuxCapabilitiesRetainSet),
+            "os/linux/capabilities-remove-set" => CapabilitySetIsolatorValue::from_json(json).map(IsolatorValue::LinuxCapabilitiesRemoveSet),
+            _ => Ok(IsolatorValue::Opaque(json.clone())),
+        }
+    }
 }
 
 impl Parseable for Isolator {
-    fn from_json(json: &Json) -> ParseResult<Isolator> {
+    fn from_json(json: &Value) -> ParseResult<Isolator> {
         match json {
-            &Json::Object(ref obj) => {
+            &Value::Object(ref obj) => {
                 let mut errors = BTreeMap::new();
                 let mut name = None;
                 let mut value = None;
 
                 match obj.get("name") {
-                    Some(json) => {
-                        match ACIdentifier::from_json(json) {
+                    Some(name_json) => {
+                        match ACIdentifier::from_json(name_json) {
                             Ok(n) => { name = Some(n); },
                             Err(err) => { errors.insert(String::from("name"), err); },
                         };
                     },
                     None => {
-                        errors.insert(String::from("name"), Errors::Node(vec![String::from("must be defined.")]));
+                        errors.insert(String::from("name"), Errors::Node(vec![String::from("must be defined")]));
                     },
                 };
 
                 match obj.get("value") {
-                    Some(json) => { value = Some(json.clone()); },
+                    Some(value_json) => {
+                        let parsed = match name {
+                            Some(ref n) => Isolator::parse_value(n, value_json),
+                            None => Ok(IsolatorValue::Opaque(value_json.clone())),
+                        };
+
+                        match parsed {
+                            Ok(v) => { value = Some(v); },
+                            Err(err) => { errors.insert(String::from("value"), err); },
+                        };
+                    },
                     None => {
-                        errors.insert(String::from("value"), Errors::Node(vec![String::from("must be defined.")]));
+                        errors.insert(String::from("value"), Errors::Node(vec![String::from("must be defined")]));
                     },
                 };
 
@@ -147,6 +516,15 @@ impl Parseable for Isolator {
     }
 }
 
+impl Serializable for Isolator {
+    fn to_json(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("name"), self.name.to_json());
+        obj.insert(String::from("value"), self.value.to_json());
+        Value::Object(obj)
+    }
+}
+
 pub struct Timestamps(DateTime<FixedOffset>);
 
 impl StringWrapper for Timestamps {
@@ -157,3 +535,9 @@ impl StringWrapper for Timestamps {
         }
     }
 }
+
+impl Renderable for Timestamps {
+    fn to_string_repr(&self) -> String {
+        self.0.to_rfc3339()
+    }
+}
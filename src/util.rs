@@ -1,5 +1,6 @@
-use rustc_serialize::json::Json;
+use serde_json::Value;
 use std::collections::BTreeMap;
+use std::fmt;
 use std::marker::Sized;
 
 /*
@@ -17,10 +18,60 @@ pub enum Errors {
     Object(BTreeMap<String, Errors>)
 }
 
+impl Errors {
+    /*
+     * Flattens this tree into a sorted list of `(pointer, message)` pairs, where `pointer` is an
+     * RFC 6901 JSON Pointer locating the node the message applies to (e.g. `/apps/0/image/id`).
+     */
+    pub fn flatten(&self) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        self.flatten_into(&String::new(), &mut out);
+        out.sort();
+        out
+    }
+
+    fn flatten_into(&self, path: &str, out: &mut Vec<(String, String)>) {
+        match *self {
+            Errors::Node(ref messages) => {
+                for message in messages {
+                    out.push((String::from(path), message.clone()));
+                }
+            },
+            Errors::Array(ref entries) => {
+                for (i, entry) in entries.iter().enumerate() {
+                    if let Some(ref err) = *entry {
+                        err.flatten_into(&format!("{}/{}", path, i), out);
+                    }
+                }
+            },
+            Errors::Object(ref fields) => {
+                for (key, err) in fields {
+                    err.flatten_into(&format!("{}/{}", path, escape_pointer_token(key)), out);
+                }
+            },
+        }
+    }
+}
+
+// Escapes a JSON Object key for use as an RFC 6901 JSON Pointer reference token.
+fn escape_pointer_token(key: &str) -> String {
+    key.replace("~", "~0").replace("/", "~1")
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines: Vec<String> = self.flatten().iter()
+            .map(|&(ref path, ref message)| format!("{}: {}", path, message))
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
 pub type ParseResult<T> = Result<T, Errors>;
 
 pub trait Parseable where Self: Sized {
-    fn from_json(json: &Json) -> ParseResult<Self>;
+    fn from_json(json: &Value) -> ParseResult<Self>;
 }
 
 pub trait StringWrapper where Self: Sized {
@@ -28,9 +79,9 @@ pub trait StringWrapper where Self: Sized {
 }
 
 impl<T> Parseable for T where T: StringWrapper {
-    fn from_json(json: &Json) -> ParseResult<T> {
+    fn from_json(json: &Value) -> ParseResult<T> {
         match json {
-            &Json::String(ref s) => T::from_string(s),
+            &Value::String(ref s) => T::from_string(s),
             _ => Err(Errors::Node(vec![String::from("must be a string")]))
         }
     }
@@ -41,3 +92,60 @@ impl StringWrapper for String {
         Ok((*s).clone())
     }
 }
+
+impl Parseable for bool {
+    fn from_json(json: &Value) -> ParseResult<bool> {
+        match json {
+            &Value::Bool(ref b) => Ok(*b),
+            _ => Err(Errors::Node(vec![String::from("must be a boolean")])),
+        }
+    }
+}
+
+impl Parseable for u64 {
+    fn from_json(json: &Value) -> ParseResult<u64> {
+        match json {
+            &Value::Number(ref n) => match n.as_u64() {
+                Some(v) => Ok(v),
+                None => Err(Errors::Node(vec![String::from("must be a positive integer")])),
+            },
+            _ => Err(Errors::Node(vec![String::from("must be a positive integer")])),
+        }
+    }
+}
+
+// Lets a `#[derive(Parseable)]` field of type `Value` carry opaque, not-yet-typed JSON through
+// unchanged.
+impl Parseable for Value {
+    fn from_json(json: &Value) -> ParseResult<Value> {
+        Ok(json.clone())
+    }
+}
+
+/*
+ * `Serializable` is the dual of `Parseable`: it renders a type back out to the `serde_json::Value`
+ * it was (or could have been) parsed from.
+ */
+pub trait Serializable {
+    fn to_json(&self) -> Value;
+}
+
+/*
+ * `Renderable` is the dual of `StringWrapper`: it renders a type back out to the canonical string
+ * form that `StringWrapper::from_string` accepts.
+ */
+pub trait Renderable {
+    fn to_string_repr(&self) -> String;
+}
+
+impl<T> Serializable for T where T: Renderable {
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string_repr())
+    }
+}
+
+impl Renderable for String {
+    fn to_string_repr(&self) -> String {
+        self.clone()
+    }
+}